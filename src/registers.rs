@@ -1,4 +1,5 @@
 use bitflags::bitflags;
+use core::fmt;
 
 bitflags! {
     /// Configuration flags of the Cr0 register.
@@ -64,43 +65,139 @@ pub struct Cr3(u64);
 
 impl Cr3 {
     const MASK: u64 = 0b1111_1111_1111;
+    const NO_FLUSH: u64 = 1 << 63;
 
-    pub fn flags(self, cr4: Cr4) -> Cr3Flags {
-        assert!(!cr4.contains(Cr4::PCID));
+    /// Returns the page-level cache flags, if `CR4.PCID` is disabled.
+    ///
+    /// Returns `None` if PCID is enabled, in which case the low bits hold the PCID instead.
+    pub fn flags(self, cr4: Cr4) -> Option<Cr3Flags> {
+        if cr4.contains(Cr4::PCID) {
+            return None;
+        }
 
-        Cr3Flags::from_bits_truncate(self.0)
+        Some(Cr3Flags::from_bits_truncate(self.0))
     }
 
-    pub fn set_flags(&mut self, cr4: Cr4, flags: Cr3Flags) {
-        assert!(!cr4.contains(Cr4::PCID));
+    /// Sets the page-level cache flags.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Cr3Error::PcidEnabled`] if `CR4.PCID` is enabled, in which case the low bits
+    /// are reserved for the PCID instead.
+    pub fn set_flags(&mut self, cr4: Cr4, flags: Cr3Flags) -> Result<(), Cr3Error> {
+        if cr4.contains(Cr4::PCID) {
+            return Err(Cr3Error::PcidEnabled);
+        }
 
         self.0 &= !Self::MASK;
         self.0 |= flags.bits() & Self::MASK;
+        Ok(())
     }
 
-    pub fn pcid(self, cr4: Cr4) -> u16 {
-        assert!(cr4.contains(Cr4::PCID));
+    /// Returns the process-context identifier, if `CR4.PCID` is enabled.
+    ///
+    /// Returns `None` if PCID is disabled, in which case the low bits hold the cache flags
+    /// instead.
+    pub fn pcid(self, cr4: Cr4) -> Option<u16> {
+        if !cr4.contains(Cr4::PCID) {
+            return None;
+        }
 
-        (self.0 & Self::MASK) as u16
+        Some((self.0 & Self::MASK) as u16)
     }
 
-    pub fn set_pcid(&mut self, cr4: Cr4, pcid: u16) {
-        assert!(cr4.contains(Cr4::PCID));
-        assert!(pcid as u64 <= Self::MASK);
+    /// Sets the process-context identifier.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Cr3Error::PcidDisabled`] if `CR4.PCID` is disabled, or
+    /// [`Cr3Error::PcidTooLarge`] if `pcid` does not fit in 12 bits.
+    pub fn set_pcid(&mut self, cr4: Cr4, pcid: u16) -> Result<(), Cr3Error> {
+        if !cr4.contains(Cr4::PCID) {
+            return Err(Cr3Error::PcidDisabled);
+        }
+
+        if pcid as u64 > Self::MASK {
+            return Err(Cr3Error::PcidTooLarge);
+        }
 
         self.0 &= !Self::MASK;
         self.0 |= pcid as u64;
+        Ok(())
+    }
+
+    /// Returns whether the "no flush" bit is set, if `CR4.PCID` is enabled.
+    ///
+    /// This bit is only meaningful while PCID is enabled: setting it before a `mov cr3`
+    /// preserves this PCID's TLB entries instead of invalidating them. Returns `None` if PCID
+    /// is disabled, in which case the bit is architecturally meaningless.
+    pub fn no_flush(self, cr4: Cr4) -> Option<bool> {
+        if !cr4.contains(Cr4::PCID) {
+            return None;
+        }
+
+        Some(self.0 & Self::NO_FLUSH != 0)
+    }
+
+    /// Sets the "no flush" bit.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Cr3Error::PcidDisabled`] if `CR4.PCID` is disabled, in which case the bit is
+    /// architecturally meaningless.
+    pub fn set_no_flush(&mut self, cr4: Cr4, no_flush: bool) -> Result<(), Cr3Error> {
+        if !cr4.contains(Cr4::PCID) {
+            return Err(Cr3Error::PcidDisabled);
+        }
+
+        if no_flush {
+            self.0 |= Self::NO_FLUSH;
+        } else {
+            self.0 &= !Self::NO_FLUSH;
+        }
+        Ok(())
     }
 
     pub fn pml4(self) -> u64 {
-        self.0 << 12
+        self.0 & !(Self::MASK | Self::NO_FLUSH)
     }
 
     pub fn set_pml4(&mut self, pml4: u64) {
         assert!(pml4 <= u64::max_value() >> 12);
 
-        self.0 &= Self::MASK;
-        self.0 |= pml4 << 12;
+        self.0 &= Self::MASK | Self::NO_FLUSH;
+        self.0 |= pml4 & !(Self::MASK | Self::NO_FLUSH);
+    }
+}
+
+/// An error accessing a part of [`Cr3`] whose meaning depends on whether `CR4.PCID` is
+/// enabled: the low bits (page-level cache flags or PCID) and the "no flush" bit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cr3Error {
+    /// The low bits hold a PCID because `CR4.PCID` is enabled, so they cannot be read/written
+    /// as cache flags.
+    PcidEnabled,
+
+    /// The low bits hold the page-level cache flags because `CR4.PCID` is disabled, so they
+    /// cannot be read/written as a PCID, and the "no flush" bit cannot be written.
+    PcidDisabled,
+
+    /// The given PCID does not fit in the 12 available bits.
+    PcidTooLarge,
+}
+
+impl fmt::Display for Cr3Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cr3Error::PcidEnabled => write!(f, "CR4.PCID is enabled, the low bits hold a PCID"),
+            Cr3Error::PcidDisabled => {
+                write!(
+                    f,
+                    "CR4.PCID is disabled, the low bits hold the page-level cache flags"
+                )
+            }
+            Cr3Error::PcidTooLarge => write!(f, "the PCID does not fit in 12 bits"),
+        }
     }
 }
 
@@ -160,7 +257,11 @@ bitflags! {
         const PCID = 1 << 17;
 
         /// Enables XSAVE and Processor Extended State.
-        const XSAVE = 1 << 18;
+        const OSXSAVE = 1 << 18;
+
+        /// Deprecated alias for [`OSXSAVE`](Self::OSXSAVE), the architecturally correct name.
+        #[deprecated(note = "renamed to `OSXSAVE` to match the architectural name")]
+        const XSAVE = Self::OSXSAVE.bits;
 
         /// Execution of code in a higher ring generates a fault.
         const SMEP = 1 << 20;
@@ -170,6 +271,19 @@ bitflags! {
 
         /// Enables Protection Key.
         const PROTECTION_KEY = 1 << 22;
+
+        /// Enables control-flow enforcement technology (CET), i.e. shadow stacks and
+        /// indirect-branch tracking.
+        const CET = 1 << 23;
+
+        /// Enables protection keys for supervisor-mode pages (PKS).
+        const PKS = 1 << 24;
+
+        /// Enables user interrupts (UINTR).
+        const UINTR = 1 << 25;
+
+        /// Enables linear address space separation (LASS).
+        const LASS = 1 << 27;
     }
 }
 
@@ -203,6 +317,107 @@ bitflags! {
     }
 }
 
+/// Checks that `cr0`, `cr4` and `efer` describe a legal, consistent control-register state.
+///
+/// The control registers have interdependencies that the CPU enforces on a `mov` to the
+/// register: writing a value that violates one of them causes a general-protection fault
+/// rather than taking effect. This lets callers validate a prospective state up front.
+///
+/// # Scope
+///
+/// This only checks invariants of a single snapshot. Two architectural rules are
+/// transition-dependent and therefore out of scope for this signature: clearing
+/// `Cr4::PCID` while PCID-tagged TLB entries still exist, and toggling
+/// `Efer::LONG_MODE_ENABLE` while `Cr0::PAGING` and PAE are already active. Catching those
+/// would require comparing against the previously loaded register state, not just the
+/// proposed one; callers that perform such transitions must order their writes correctly
+/// themselves (e.g. disable paging before toggling `LONG_MODE_ENABLE`, and flush the TLB
+/// before disabling PCID).
+///
+/// # Errors
+///
+/// Returns an error describing the first violated invariant.
+pub fn validate(cr0: Cr0, cr4: Cr4, efer: Efer) -> Result<(), ConsistencyError> {
+    if efer.contains(Efer::LONG_MODE_ACTIVE) {
+        return Err(ConsistencyError::LongModeActiveIsReadOnly);
+    }
+
+    if cr0.contains(Cr0::PAGING) && !cr0.contains(Cr0::PROTECTED_MODE_ENABLE) {
+        return Err(ConsistencyError::PagingRequiresProtectedMode);
+    }
+
+    let long_mode_paging = efer.contains(Efer::LONG_MODE_ENABLE) && cr0.contains(Cr0::PAGING);
+
+    if long_mode_paging && !cr4.contains(Cr4::PHYSICAL_ADDRESS_EXTENSION) {
+        return Err(ConsistencyError::LongModePagingRequiresPae);
+    }
+
+    if cr4.contains(Cr4::FIVE_LEVEL_PAGING)
+        && !(cr4.contains(Cr4::PHYSICAL_ADDRESS_EXTENSION) && long_mode_paging)
+    {
+        return Err(ConsistencyError::FiveLevelPagingRequiresLongModePae);
+    }
+
+    if cr4.contains(Cr4::PCID) && !long_mode_paging {
+        return Err(ConsistencyError::PcidRequiresLongMode);
+    }
+
+    Ok(())
+}
+
+/// An architectural invariant shared between [`Cr0`], [`Cr4`] and [`Efer`] was violated.
+///
+/// This only covers invariants that [`validate`] can check from a single snapshot; see its
+/// "Scope" section for the transition-dependent rules that are not represented here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsistencyError {
+    /// `Efer::LONG_MODE_ACTIVE` is a CPU-owned status bit and cannot be set directly.
+    LongModeActiveIsReadOnly,
+
+    /// `Cr0::PAGING` requires `Cr0::PROTECTED_MODE_ENABLE`.
+    PagingRequiresProtectedMode,
+
+    /// `Efer::LONG_MODE_ENABLE` together with `Cr0::PAGING` requires
+    /// `Cr4::PHYSICAL_ADDRESS_EXTENSION`.
+    LongModePagingRequiresPae,
+
+    /// `Cr4::FIVE_LEVEL_PAGING` requires PAE and active long-mode paging.
+    FiveLevelPagingRequiresLongModePae,
+
+    /// `Cr4::PCID` may only be set while long mode is active with paging enabled.
+    PcidRequiresLongMode,
+}
+
+impl fmt::Display for ConsistencyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConsistencyError::LongModeActiveIsReadOnly => {
+                write!(
+                    f,
+                    "Efer::LONG_MODE_ACTIVE cannot be set directly, it is owned by the CPU"
+                )
+            }
+            ConsistencyError::PagingRequiresProtectedMode => {
+                write!(f, "Cr0::PAGING requires Cr0::PROTECTED_MODE_ENABLE")
+            }
+            ConsistencyError::LongModePagingRequiresPae => write!(
+                f,
+                "Efer::LONG_MODE_ENABLE with Cr0::PAGING requires Cr4::PHYSICAL_ADDRESS_EXTENSION"
+            ),
+            ConsistencyError::FiveLevelPagingRequiresLongModePae => write!(
+                f,
+                "Cr4::FIVE_LEVEL_PAGING requires physical address extension and active long-mode paging"
+            ),
+            ConsistencyError::PcidRequiresLongMode => {
+                write!(
+                    f,
+                    "Cr4::PCID requires long mode to be active with paging enabled"
+                )
+            }
+        }
+    }
+}
+
 bitflags! {
     /// The RFLAGS register.
     pub struct RFlags: u64 {
@@ -292,3 +507,419 @@ impl Default for RFlags {
         }
     }
 }
+
+bitflags! {
+    /// State-component flags of the XCR0 extended control register.
+    ///
+    /// XCR0 is read and written with the `xgetbv`/`xsetbv` instructions and
+    /// selects which processor state is saved and restored by `xsave`/`xrstor`.
+    #[derive(Default)]
+    pub struct XCr0: u64 {
+        /// Legacy x87 floating-point unit state. Must always be set.
+        const X87 = 1 << 0;
+
+        /// SSE state, i.e. the XMM registers and `MXCSR`.
+        const SSE = 1 << 1;
+
+        /// AVX state, i.e. the upper halves of the YMM registers.
+        const AVX = 1 << 2;
+
+        /// MPX bounds registers `BND0`-`BND3`.
+        const BNDREG = 1 << 3;
+
+        /// MPX bounds configuration and status registers `BNDCFGU`/`BNDSTATUS`.
+        const BNDCSR = 1 << 4;
+
+        /// AVX-512 opmask registers `k0`-`k7`.
+        const OPMASK = 1 << 5;
+
+        /// Upper halves of the lower 16 ZMM registers (AVX-512 state).
+        const ZMM_HI256 = 1 << 6;
+
+        /// Upper 16 ZMM registers `ZMM16`-`ZMM31` (AVX-512 state).
+        const HI16_ZMM = 1 << 7;
+
+        /// Protection key rights register `PKRU`.
+        const PKRU = 1 << 9;
+    }
+}
+
+impl XCr0 {
+    /// Checks that this value describes a legal XSAVE state-component configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a required dependency between state components is violated.
+    pub fn validate(self) -> Result<(), XCr0Error> {
+        if !self.contains(XCr0::X87) {
+            return Err(XCr0Error::X87Required);
+        }
+
+        if self.contains(XCr0::AVX) && !self.contains(XCr0::SSE) {
+            return Err(XCr0Error::AvxRequiresSse);
+        }
+
+        if self.contains(XCr0::BNDREG) != self.contains(XCr0::BNDCSR) {
+            return Err(XCr0Error::MpxRequiresBothComponents);
+        }
+
+        let avx512 = XCr0::OPMASK | XCr0::ZMM_HI256 | XCr0::HI16_ZMM;
+        let avx512_bits = self & avx512;
+        if !avx512_bits.is_empty() {
+            if !self.contains(XCr0::AVX) {
+                return Err(XCr0Error::Avx512RequiresAvx);
+            }
+
+            if avx512_bits != avx512 {
+                return Err(XCr0Error::Avx512RequiresAllComponents);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// An architectural invariant of [`XCr0`] was violated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XCr0Error {
+    /// `X87` must always be set.
+    X87Required,
+
+    /// `AVX` requires `SSE` to be enabled.
+    AvxRequiresSse,
+
+    /// `BNDREG` and `BNDCSR` must be enabled or disabled together.
+    MpxRequiresBothComponents,
+
+    /// The AVX-512 components require `AVX` to be enabled.
+    Avx512RequiresAvx,
+
+    /// The AVX-512 components (`OPMASK`, `ZMM_HI256`, `HI16_ZMM`) must all be
+    /// enabled or all disabled together.
+    Avx512RequiresAllComponents,
+}
+
+impl fmt::Display for XCr0Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            XCr0Error::X87Required => write!(f, "the X87 state component must always be set"),
+            XCr0Error::AvxRequiresSse => write!(f, "AVX requires SSE to be enabled"),
+            XCr0Error::MpxRequiresBothComponents => {
+                write!(f, "BNDREG and BNDCSR must be enabled or disabled together")
+            }
+            XCr0Error::Avx512RequiresAvx => write!(f, "AVX-512 state components require AVX"),
+            XCr0Error::Avx512RequiresAllComponents => write!(
+                f,
+                "AVX-512 state components (OPMASK, ZMM_HI256, HI16_ZMM) must all be enabled or all disabled together"
+            ),
+        }
+    }
+}
+
+bitflags! {
+    /// The `MXCSR` register, controlling and reporting SSE floating-point state.
+    pub struct Mxcsr: u32 {
+        /// Set if an invalid operation was detected.
+        const INVALID_OPERATION = 1 << 0;
+
+        /// Set if a denormal operand was detected.
+        const DENORMAL = 1 << 1;
+
+        /// Set if a divide-by-zero was detected.
+        const DIVIDE_BY_ZERO = 1 << 2;
+
+        /// Set if an overflow was detected.
+        const OVERFLOW = 1 << 3;
+
+        /// Set if an underflow was detected.
+        const UNDERFLOW = 1 << 4;
+
+        /// Set if a precision (inexact result) condition was detected.
+        const PRECISION = 1 << 5;
+
+        /// Treats denormal source operands as zero.
+        const DENORMALS_ARE_ZERO = 1 << 6;
+
+        /// Disables the invalid-operation exception.
+        const INVALID_OPERATION_MASK = 1 << 7;
+
+        /// Disables the denormal-operand exception.
+        const DENORMAL_MASK = 1 << 8;
+
+        /// Disables the divide-by-zero exception.
+        const DIVIDE_BY_ZERO_MASK = 1 << 9;
+
+        /// Disables the overflow exception.
+        const OVERFLOW_MASK = 1 << 10;
+
+        /// Disables the underflow exception.
+        const UNDERFLOW_MASK = 1 << 11;
+
+        /// Disables the precision exception.
+        const PRECISION_MASK = 1 << 12;
+
+        /// Flushes underflowing results to zero instead of generating a denormal.
+        const FLUSH_TO_ZERO = 1 << 15;
+    }
+}
+
+/// The rounding mode controlled by bits 13-14 of [`Mxcsr`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Round to the nearest representable value, ties to even.
+    ToNearest,
+
+    /// Round towards negative infinity.
+    Down,
+
+    /// Round towards positive infinity.
+    Up,
+
+    /// Round towards zero (truncate).
+    TowardZero,
+}
+
+impl Mxcsr {
+    /// Returns the floating-point rounding mode.
+    pub fn rounding_mode(self) -> RoundingMode {
+        match (self.bits >> 13) & 0b11 {
+            0b00 => RoundingMode::ToNearest,
+            0b01 => RoundingMode::Down,
+            0b10 => RoundingMode::Up,
+            0b11 => RoundingMode::TowardZero,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Sets the floating-point rounding mode.
+    pub fn set_rounding_mode(&mut self, mode: RoundingMode) {
+        self.bits &= !(0b11 << 13);
+        self.bits |= (mode as u32) << 13;
+    }
+}
+
+impl Default for Mxcsr {
+    /// The power-on value: all exceptions masked, round-to-nearest.
+    fn default() -> Self {
+        Self {
+            bits: Mxcsr::INVALID_OPERATION_MASK.bits
+                | Mxcsr::DENORMAL_MASK.bits
+                | Mxcsr::DIVIDE_BY_ZERO_MASK.bits
+                | Mxcsr::OVERFLOW_MASK.bits
+                | Mxcsr::UNDERFLOW_MASK.bits
+                | Mxcsr::PRECISION_MASK.bits,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xcr0_x87_required() {
+        assert_eq!(XCr0::empty().validate(), Err(XCr0Error::X87Required));
+        assert_eq!(XCr0::SSE.validate(), Err(XCr0Error::X87Required));
+    }
+
+    #[test]
+    fn xcr0_avx_requires_sse() {
+        assert_eq!(
+            (XCr0::X87 | XCr0::AVX).validate(),
+            Err(XCr0Error::AvxRequiresSse)
+        );
+        assert_eq!((XCr0::X87 | XCr0::SSE | XCr0::AVX).validate(), Ok(()));
+    }
+
+    #[test]
+    fn xcr0_mpx_requires_both_components() {
+        assert_eq!(
+            (XCr0::X87 | XCr0::BNDREG).validate(),
+            Err(XCr0Error::MpxRequiresBothComponents)
+        );
+        assert_eq!(
+            (XCr0::X87 | XCr0::BNDCSR).validate(),
+            Err(XCr0Error::MpxRequiresBothComponents)
+        );
+        assert_eq!((XCr0::X87 | XCr0::BNDREG | XCr0::BNDCSR).validate(), Ok(()));
+    }
+
+    #[test]
+    fn xcr0_avx512_requires_avx() {
+        assert_eq!(
+            (XCr0::X87 | XCr0::SSE | XCr0::OPMASK).validate(),
+            Err(XCr0Error::Avx512RequiresAvx)
+        );
+    }
+
+    #[test]
+    fn xcr0_avx512_requires_all_components() {
+        let base = XCr0::X87 | XCr0::SSE | XCr0::AVX;
+        assert_eq!(
+            (base | XCr0::OPMASK).validate(),
+            Err(XCr0Error::Avx512RequiresAllComponents)
+        );
+        assert_eq!(
+            (base | XCr0::OPMASK | XCr0::ZMM_HI256 | XCr0::HI16_ZMM).validate(),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn consistency_long_mode_active_is_read_only() {
+        assert_eq!(
+            validate(Cr0::empty(), Cr4::empty(), Efer::LONG_MODE_ACTIVE),
+            Err(ConsistencyError::LongModeActiveIsReadOnly)
+        );
+    }
+
+    #[test]
+    fn consistency_paging_requires_protected_mode() {
+        assert_eq!(
+            validate(Cr0::PAGING, Cr4::empty(), Efer::empty()),
+            Err(ConsistencyError::PagingRequiresProtectedMode)
+        );
+    }
+
+    #[test]
+    fn consistency_long_mode_paging_requires_pae() {
+        let cr0 = Cr0::PROTECTED_MODE_ENABLE | Cr0::PAGING;
+        assert_eq!(
+            validate(cr0, Cr4::empty(), Efer::LONG_MODE_ENABLE),
+            Err(ConsistencyError::LongModePagingRequiresPae)
+        );
+        assert_eq!(
+            validate(cr0, Cr4::PHYSICAL_ADDRESS_EXTENSION, Efer::LONG_MODE_ENABLE),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn consistency_five_level_paging_requires_long_mode_pae() {
+        let cr0 = Cr0::PROTECTED_MODE_ENABLE | Cr0::PAGING;
+        assert_eq!(
+            validate(cr0, Cr4::FIVE_LEVEL_PAGING, Efer::empty()),
+            Err(ConsistencyError::FiveLevelPagingRequiresLongModePae)
+        );
+
+        let cr4 = Cr4::PHYSICAL_ADDRESS_EXTENSION | Cr4::FIVE_LEVEL_PAGING;
+        assert_eq!(validate(cr0, cr4, Efer::LONG_MODE_ENABLE), Ok(()));
+    }
+
+    #[test]
+    fn consistency_pcid_requires_long_mode() {
+        let cr0 = Cr0::PROTECTED_MODE_ENABLE | Cr0::PAGING;
+        assert_eq!(
+            validate(cr0, Cr4::PCID, Efer::empty()),
+            Err(ConsistencyError::PcidRequiresLongMode)
+        );
+
+        let cr4 = Cr4::PHYSICAL_ADDRESS_EXTENSION | Cr4::PCID;
+        assert_eq!(validate(cr0, cr4, Efer::LONG_MODE_ENABLE), Ok(()));
+    }
+
+    #[test]
+    fn cr3_no_flush_requires_pcid() {
+        let mut cr3 = Cr3::default();
+        assert_eq!(cr3.no_flush(Cr4::empty()), None);
+        assert_eq!(
+            cr3.set_no_flush(Cr4::empty(), true),
+            Err(Cr3Error::PcidDisabled)
+        );
+    }
+
+    #[test]
+    fn cr3_no_flush_survives_set_pml4() {
+        let mut cr3 = Cr3::default();
+        cr3.set_no_flush(Cr4::PCID, true).unwrap();
+        cr3.set_pml4(0x0dea_d000);
+        assert_eq!(cr3.no_flush(Cr4::PCID), Some(true));
+        assert_eq!(cr3.pml4(), 0x0dea_d000);
+    }
+
+    #[test]
+    fn cr3_pml4_round_trips() {
+        let mut cr3 = Cr3::default();
+        cr3.set_pml4(0x0dea_d000);
+        assert_eq!(cr3.pml4(), 0x0dea_d000);
+    }
+
+    #[test]
+    fn cr3_set_pml4_does_not_leak_into_no_flush() {
+        let mut cr3 = Cr3::default();
+        cr3.set_no_flush(Cr4::PCID, false).unwrap();
+        cr3.set_pml4(u64::max_value() >> 12);
+        assert_eq!(cr3.no_flush(Cr4::PCID), Some(false));
+    }
+
+    #[test]
+    fn cr3_pcid_and_flags_are_mode_gated() {
+        let mut cr3 = Cr3::default();
+        assert_eq!(cr3.pcid(Cr4::empty()), None);
+        assert_eq!(cr3.set_pcid(Cr4::empty(), 1), Err(Cr3Error::PcidDisabled));
+        assert_eq!(cr3.flags(Cr4::PCID), None);
+        assert_eq!(
+            cr3.set_flags(Cr4::PCID, Cr3Flags::PAGE_LEVEL_CACHE_DISABLE),
+            Err(Cr3Error::PcidEnabled)
+        );
+
+        cr3.set_pcid(Cr4::PCID, 0x0ab).unwrap();
+        assert_eq!(cr3.pcid(Cr4::PCID), Some(0x0ab));
+    }
+
+    #[test]
+    fn cr3_set_pcid_rejects_oversized_pcid() {
+        let mut cr3 = Cr3::default();
+        assert_eq!(cr3.set_pcid(Cr4::PCID, 0x1000), Err(Cr3Error::PcidTooLarge));
+    }
+
+    #[test]
+    fn cr4_security_extension_bits() {
+        assert_eq!(Cr4::CET.bits(), 1 << 23);
+        assert_eq!(Cr4::PKS.bits(), 1 << 24);
+        assert_eq!(Cr4::UINTR.bits(), 1 << 25);
+        assert_eq!(Cr4::LASS.bits(), 1 << 27);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn cr4_xsave_is_an_alias_for_osxsave() {
+        assert_eq!(Cr4::XSAVE.bits(), Cr4::OSXSAVE.bits());
+    }
+
+    #[test]
+    fn mxcsr_default_is_power_on_value() {
+        let mxcsr = Mxcsr::default();
+        assert_eq!(mxcsr.rounding_mode(), RoundingMode::ToNearest);
+        assert!(mxcsr.contains(Mxcsr::INVALID_OPERATION_MASK));
+        assert!(mxcsr.contains(Mxcsr::DENORMAL_MASK));
+        assert!(mxcsr.contains(Mxcsr::DIVIDE_BY_ZERO_MASK));
+        assert!(mxcsr.contains(Mxcsr::OVERFLOW_MASK));
+        assert!(mxcsr.contains(Mxcsr::UNDERFLOW_MASK));
+        assert!(mxcsr.contains(Mxcsr::PRECISION_MASK));
+        assert!(!mxcsr.contains(Mxcsr::FLUSH_TO_ZERO));
+    }
+
+    #[test]
+    fn mxcsr_rounding_mode_round_trips() {
+        let mut mxcsr = Mxcsr::default();
+        for mode in [
+            RoundingMode::ToNearest,
+            RoundingMode::Down,
+            RoundingMode::Up,
+            RoundingMode::TowardZero,
+        ] {
+            mxcsr.set_rounding_mode(mode);
+            assert_eq!(mxcsr.rounding_mode(), mode);
+        }
+    }
+
+    #[test]
+    fn mxcsr_set_rounding_mode_does_not_disturb_other_bits() {
+        let mut mxcsr = Mxcsr::default() | Mxcsr::FLUSH_TO_ZERO | Mxcsr::INVALID_OPERATION;
+        mxcsr.set_rounding_mode(RoundingMode::Up);
+        assert!(mxcsr.contains(Mxcsr::FLUSH_TO_ZERO));
+        assert!(mxcsr.contains(Mxcsr::INVALID_OPERATION));
+        assert_eq!(mxcsr.rounding_mode(), RoundingMode::Up);
+    }
+}